@@ -292,6 +292,73 @@ impl MoveHarness {
         self.run(txn)
     }
 
+    /// Builds the Move Package found at the given path and returns the metadata and the code of
+    /// an incremental upgrade, which only include the modules named in `changed_modules`.
+    pub fn build_incremental_update(
+        path: &Path,
+        changed_modules: &[&str],
+    ) -> (PackageMetadata, Vec<Vec<u8>>) {
+        let package = BuiltPackage::build(path.to_owned(), BuildOptions::default())
+            .expect("building package must succeed");
+        let code = package
+            .modules()
+            .filter(|m| changed_modules.contains(&m.self_id().name().as_str()))
+            .map(|m| {
+                let mut bytes = vec![];
+                m.serialize(&mut bytes).expect("module must serialize");
+                bytes
+            })
+            .collect();
+        let mut metadata = package
+            .extract_metadata()
+            .expect("extracting package metadata must succeed");
+        metadata
+            .modules
+            .retain(|m| changed_modules.contains(&m.name.as_str()));
+        (metadata, code)
+    }
+
+    /// Creates a transaction which publishes an incremental upgrade of the Move Package found at
+    /// the given path on behalf of the given account. Only the modules named in `changed_modules`
+    /// are included in the metadata and the code bundle.
+    pub fn create_publish_package_incremental(
+        &mut self,
+        account: &Account,
+        path: &Path,
+        changed_modules: &[&str],
+        removed_modules: &[&str],
+    ) -> SignedTransaction {
+        let (metadata, code) = Self::build_incremental_update(path, changed_modules);
+        self.create_transaction_payload(
+            account,
+            aptos_stdlib::code_publish_package_incremental_txn(
+                bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+                removed_modules
+                    .iter()
+                    .map(|name| name.as_bytes().to_vec())
+                    .collect(),
+                code,
+            ),
+        )
+    }
+
+    /// Runs transaction which publishes an incremental upgrade of the Move Package.
+    pub fn publish_package_incremental(
+        &mut self,
+        account: &Account,
+        path: &Path,
+        changed_modules: &[&str],
+        removed_modules: &[&str],
+    ) -> TransactionStatus {
+        let txn = self.create_publish_package_incremental(
+            account,
+            path,
+            changed_modules,
+            removed_modules,
+        );
+        self.run(txn)
+    }
+
     pub fn evaluate_publish_gas(&mut self, account: &Account, path: &Path) -> u64 {
         let txn = self.create_publish_package(account, path, None, |_| {});
         let output = self.run_raw(txn);
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{assert_abort, assert_success, assert_vm_status, tests::common, MoveHarness};
-use aptos_framework::natives::code::{PackageRegistry, UpgradePolicy};
+use aptos_framework::natives::code::{PackageMetadata, PackageRegistry, UpgradePolicy};
 use aptos_package_builder::PackageBuilder;
 use aptos_types::account_address::{create_resource_address, AccountAddress};
 use aptos_types::on_chain_config::FeatureFlag;
@@ -307,3 +307,141 @@ fn code_publishing_friend_as_private(enabled: Vec<FeatureFlag>, disabled: Vec<Fe
         assert_vm_status!(result, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
     }
 }
+
+/// Builds a package at 0xcafe with the given modules, each given as (name, source).
+fn build_incremental_pack(modules: &[(&str, &str)]) -> tempfile::TempDir {
+    let mut pack = PackageBuilder::new("Package").with_policy(UpgradePolicy::compat());
+    for (name, source) in modules {
+        pack.add_source(name, source);
+    }
+    pack.write_to_temp().unwrap()
+}
+
+fn read_registry(h: &MoveHarness, acc: &AccountAddress) -> PackageRegistry {
+    h.read_resource::<PackageRegistry>(acc, parse_struct_tag("0x1::code::PackageRegistry").unwrap())
+        .unwrap()
+}
+
+#[rstest(enabled, disabled,
+         case(vec![], vec![FeatureFlag::CODE_DEPENDENCY_CHECK]),
+         case(vec![FeatureFlag::CODE_DEPENDENCY_CHECK], vec![]),
+)]
+fn code_publishing_incremental_add(enabled: Vec<FeatureFlag>, disabled: Vec<FeatureFlag>) {
+    let mut h = MoveHarness::new_with_features(enabled, disabled);
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let pack1_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        ("b", "module 0xcafe::b { public fun f() {} }"),
+    ]);
+    assert_success!(h.publish_package(&acc, pack1_dir.path()));
+    let initial = read_registry(&h, acc.address()).packages.remove(0);
+
+    // Only the new module `c` is sent, which depends on the unchanged module `a`.
+    let pack2_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        ("b", "module 0xcafe::b { public fun f() {} }"),
+        (
+            "c",
+            "module 0xcafe::c { use 0xcafe::a; public fun f() { a::f() } }",
+        ),
+    ]);
+    assert_success!(h.publish_package_incremental(&acc, pack2_dir.path(), &["c"], &[]));
+
+    let registry = read_registry(&h, acc.address());
+    assert_eq!(registry.packages.len(), 1);
+    assert_eq!(registry.packages[0].upgrade_number, 1);
+    let names = registry.packages[0]
+        .modules
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["a", "b", "c"]);
+
+    // The on-chain overlay matches the off-chain one.
+    let (update, _) = MoveHarness::build_incremental_update(pack2_dir.path(), &["c"]);
+    let expected = PackageMetadata::merge_incremental(&initial, update, &[]).unwrap();
+    assert_eq!(registry.packages[0], expected);
+}
+
+#[test]
+fn code_publishing_incremental_replace() {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let pack1_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        ("b", "module 0xcafe::b { public fun f() {} }"),
+    ]);
+    assert_success!(h.publish_package(&acc, pack1_dir.path()));
+    let initial = read_registry(&h, acc.address()).packages.remove(0);
+
+    // Compatible change of `b` only.
+    let pack2_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        (
+            "b",
+            "module 0xcafe::b { public fun f() {} public fun g() {} }",
+        ),
+    ]);
+    assert_success!(h.publish_package_incremental(&acc, pack2_dir.path(), &["b"], &[]));
+
+    let upgraded = read_registry(&h, acc.address()).packages.remove(0);
+    assert_eq!(upgraded.upgrade_number, 1);
+    assert_eq!(upgraded.modules.len(), 2);
+    assert_eq!(upgraded.modules[0], initial.modules[0]);
+    assert_eq!(upgraded.modules[1].name, "b");
+    assert_ne!(upgraded.modules[1], initial.modules[1]);
+
+    // The on-chain overlay matches the off-chain one.
+    let (update, _) = MoveHarness::build_incremental_update(pack2_dir.path(), &["b"]);
+    let expected = PackageMetadata::merge_incremental(&initial, update, &[]).unwrap();
+    assert_eq!(upgraded, expected);
+
+    // An incompatible change is still rejected.
+    let pack3_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        ("b", "module 0xcafe::b { public fun g() {} }"),
+    ]);
+    let status = h.publish_package_incremental(&acc, pack3_dir.path(), &["b"], &[]);
+    assert_vm_status!(status, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
+}
+
+#[test]
+fn code_publishing_incremental_tombstone() {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let pack1_dir = build_incremental_pack(&[
+        ("a", "module 0xcafe::a { public fun f() {} }"),
+        ("b", "module 0xcafe::b { public fun f() {} }"),
+    ]);
+    assert_success!(h.publish_package(&acc, pack1_dir.path()));
+
+    // Removing a module is not allowed under the `compatible` policy.
+    let status = h.publish_package_incremental(&acc, pack1_dir.path(), &[], &["b"]);
+    assert_abort!(status, 0x4);
+
+    // A removed module must be part of the package.
+    let status = h.publish_package_incremental(&acc, pack1_dir.path(), &[], &["c"]);
+    assert_abort!(status, 0x1000A);
+
+    // A removed module cannot be republished at the same time.
+    let status = h.publish_package_incremental(&acc, pack1_dir.path(), &["b"], &["b"]);
+    assert_abort!(status, 0x1000A);
+
+    // Nothing has changed in the registry.
+    let registry = read_registry(&h, acc.address());
+    assert_eq!(registry.packages[0].upgrade_number, 0);
+    assert_eq!(registry.packages[0].modules.len(), 2);
+}
+
+#[test]
+fn code_publishing_incremental_unknown_package() {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    let pack_dir = build_incremental_pack(&[("a", "module 0xcafe::a { public fun f() {} }")]);
+    let status = h.publish_package_incremental(&acc, pack_dir.path(), &["a"], &[]);
+    assert_abort!(status, 0x60009);
+}
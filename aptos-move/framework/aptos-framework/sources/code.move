@@ -90,6 +90,12 @@ module aptos_framework::code {
     /// Creating a package with incompatible upgrade policy is disabled.
     const EINCOMPATIBLE_POLICY_DISABLED: u64 = 0x8;
 
+    /// An incremental upgrade can only be applied to a package which has already been published
+    const EPACKAGE_NOT_FOUND: u64 = 0x9;
+
+    /// A module marked for removal in an incremental upgrade is not part of the package, or is also republished
+    const EINVALID_REMOVED_MODULE: u64 = 0xA;
+
     /// Whether unconditional code upgrade with no compatibility check is allowed. This
     /// publication mode should only be used for modules which aren't shared with user others.
     /// The developer is responsible for not breaking memory layout of any resources he already
@@ -190,6 +196,83 @@ module aptos_framework::code {
         publish_package(owner, util::from_bytes<PackageMetadata>(metadata_serialized), code)
     }
 
+    /// Publishes an incremental upgrade of a package which already exists at the signer's address. Unlike
+    /// `publish_package`, the metadata and the code only need to contain the modules which are added or
+    /// replaced. Modules of the existing package which are not mentioned are kept unchanged. Removing a module
+    /// must be requested explicitly via `removed_modules`, and is subject to the upgrade policy of the package.
+    public fun publish_package_incremental(
+        owner: &signer,
+        pack: PackageMetadata,
+        removed_modules: vector<String>,
+        code: vector<vector<u8>>
+    ) acquires PackageRegistry {
+        // Disallow incompatible upgrade mode. Governance can decide later if this should be reconsidered.
+        assert!(
+            pack.upgrade_policy.policy > upgrade_policy_arbitrary().policy,
+            error::invalid_argument(EINCOMPATIBLE_POLICY_DISABLED),
+        );
+
+        let addr = signer::address_of(owner);
+        assert!(exists<PackageRegistry>(addr), error::not_found(EPACKAGE_NOT_FOUND));
+
+        // Checks for valid dependencies to other packages
+        let allowed_deps = check_dependencies(addr, &pack);
+
+        // Check the changed modules against conflicts
+        let module_names = get_module_names(&pack);
+        let packages = &mut borrow_global_mut<PackageRegistry>(addr).packages;
+        let len = vector::length(packages);
+        let index = len;
+        let i = 0;
+        while (i < len) {
+            let old = vector::borrow(packages, i);
+            if (old.name == pack.name) {
+                check_incremental_upgradability(old, &pack, &module_names, &removed_modules);
+                index = i;
+            } else {
+                check_coexistence(old, &module_names)
+            };
+            i = i + 1;
+        };
+        assert!(index < len, error::not_found(EPACKAGE_NOT_FOUND));
+
+        // Overlay the changed modules onto the existing package and update the registry
+        let old = vector::borrow_mut(packages, index);
+        let upgrade_number = old.upgrade_number + 1;
+        let PackageMetadata {
+            name, upgrade_policy, upgrade_number: _, source_digest, manifest, modules, deps, extension
+        } = pack;
+        let modules = merge_modules(&mut old.modules, modules, &removed_modules);
+
+        // Modules which are kept from the existing package are valid dependencies of the changed ones
+        let i = 0;
+        while (i < vector::length(&modules)) {
+            let module_name = vector::borrow(&modules, i).name;
+            vector::push_back(&mut allowed_deps, AllowedDep { account: addr, module_name });
+            i = i + 1;
+        };
+        *old = PackageMetadata {
+            name, upgrade_policy, upgrade_number, source_digest, manifest, modules, deps, extension
+        };
+
+        // Request publish of the changed modules only
+        if (features::code_dependency_check_enabled())
+            request_publish_with_allowed_deps(addr, module_names, allowed_deps, code, upgrade_policy.policy)
+        else
+            request_publish(addr, module_names, code, upgrade_policy.policy)
+    }
+
+    /// Same as `publish_package_incremental` but as an entry function which can be called as a transaction.
+    public entry fun publish_package_incremental_txn(
+        owner: &signer,
+        metadata_serialized: vector<u8>,
+        removed_modules: vector<String>,
+        code: vector<vector<u8>>
+    ) acquires PackageRegistry {
+        publish_package_incremental(
+            owner, util::from_bytes<PackageMetadata>(metadata_serialized), removed_modules, code)
+    }
+
     // Helpers
     // -------
 
@@ -211,6 +294,70 @@ module aptos_framework::code {
         }
     }
 
+    /// Checks whether the given incremental upgrade can be applied to the old package. The module set after
+    /// the upgrade is subject to the same rules as a full upgrade.
+    fun check_incremental_upgradability(
+        old_pack: &PackageMetadata,
+        new_pack: &PackageMetadata,
+        changed_modules: &vector<String>,
+        removed_modules: &vector<String>
+    ) {
+        let old_modules = get_module_names(old_pack);
+        let i = 0;
+        while (i < vector::length(removed_modules)) {
+            let name = vector::borrow(removed_modules, i);
+            assert!(
+                vector::contains(&old_modules, name) && !vector::contains(changed_modules, name),
+                error::invalid_argument(EINVALID_REMOVED_MODULE)
+            );
+            i = i + 1;
+        };
+        let new_modules = *changed_modules;
+        let i = 0;
+        while (i < vector::length(&old_modules)) {
+            let name = vector::borrow(&old_modules, i);
+            if (!vector::contains(removed_modules, name) && !vector::contains(changed_modules, name)) {
+                vector::push_back(&mut new_modules, *name)
+            };
+            i = i + 1;
+        };
+        check_upgradability(old_pack, new_pack, &new_modules)
+    }
+
+    /// Overlays the changed modules onto the old ones by name. Replaced modules keep their position, added
+    /// modules are appended, and removed modules are dropped.
+    fun merge_modules(
+        old_modules: &mut vector<ModuleMetadata>,
+        changed_modules: vector<ModuleMetadata>,
+        removed_modules: &vector<String>
+    ): vector<ModuleMetadata> {
+        let merged = vector::empty();
+        vector::reverse(old_modules);
+        while (!vector::is_empty(old_modules)) {
+            let old_mod = vector::pop_back(old_modules);
+            let (replaced, j) = index_of_module(&changed_modules, &old_mod.name);
+            if (replaced) {
+                vector::push_back(&mut merged, vector::remove(&mut changed_modules, j))
+            } else if (!vector::contains(removed_modules, &old_mod.name)) {
+                vector::push_back(&mut merged, old_mod)
+            }
+        };
+        vector::append(&mut merged, changed_modules);
+        merged
+    }
+
+    /// Returns whether a module with the given name is in the list, and its index.
+    fun index_of_module(modules: &vector<ModuleMetadata>, name: &String): (bool, u64) {
+        let i = 0;
+        while (i < vector::length(modules)) {
+            if (&vector::borrow(modules, i).name == name) {
+                return (true, i)
+            };
+            i = i + 1;
+        };
+        (false, 0)
+    }
+
     /// Checks whether a new package with given names can co-exist with old package.
     fun check_coexistence(old_pack: &PackageMetadata, new_modules: &vector<String>) {
         // The modules introduced by each package must not overlap with `names`.
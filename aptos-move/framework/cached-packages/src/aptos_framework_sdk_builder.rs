@@ -135,6 +135,13 @@ pub enum EntryFunctionCall {
         should_pass: bool,
     },
 
+    /// Same as `publish_package_incremental` but as an entry function which can be called as a transaction.
+    CodePublishPackageIncrementalTxn {
+        metadata_serialized: Vec<u8>,
+        removed_modules: Vec<Vec<u8>>,
+        code: Vec<Vec<u8>>,
+    },
+
     /// Same as `publish_package` but as an entry function which can be called as a transaction. Because
     /// of current restrictions for txn parameters, the metadata needs to be passed in serialized form.
     CodePublishPackageTxn {
@@ -544,6 +551,11 @@ impl EntryFunctionCall {
                 proposal_id,
                 should_pass,
             } => aptos_governance_vote(stake_pool, proposal_id, should_pass),
+            CodePublishPackageIncrementalTxn {
+                metadata_serialized,
+                removed_modules,
+                code,
+            } => code_publish_package_incremental_txn(metadata_serialized, removed_modules, code),
             CodePublishPackageTxn {
                 metadata_serialized,
                 code,
@@ -1040,6 +1052,30 @@ pub fn aptos_governance_vote(
     ))
 }
 
+/// Same as `publish_package_incremental` but as an entry function which can be called as a transaction.
+pub fn code_publish_package_incremental_txn(
+    metadata_serialized: Vec<u8>,
+    removed_modules: Vec<Vec<u8>>,
+    code: Vec<Vec<u8>>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("code").to_owned(),
+        ),
+        ident_str!("publish_package_incremental_txn").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&metadata_serialized).unwrap(),
+            bcs::to_bytes(&removed_modules).unwrap(),
+            bcs::to_bytes(&code).unwrap(),
+        ],
+    ))
+}
+
 /// Same as `publish_package` but as an entry function which can be called as a transaction. Because
 /// of current restrictions for txn parameters, the metadata needs to be passed in serialized form.
 pub fn code_publish_package_txn(
@@ -2302,6 +2338,20 @@ mod decoder {
         }
     }
 
+    pub fn code_publish_package_incremental_txn(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::CodePublishPackageIncrementalTxn {
+                metadata_serialized: bcs::from_bytes(script.args().get(0)?).ok()?,
+                removed_modules: bcs::from_bytes(script.args().get(1)?).ok()?,
+                code: bcs::from_bytes(script.args().get(2)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn code_publish_package_txn(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
         if let TransactionPayload::EntryFunction(script) = payload {
             Some(EntryFunctionCall::CodePublishPackageTxn {
@@ -3011,6 +3061,10 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "aptos_governance_vote".to_string(),
             Box::new(decoder::aptos_governance_vote),
         );
+        map.insert(
+            "code_publish_package_incremental_txn".to_string(),
+            Box::new(decoder::code_publish_package_incremental_txn),
+        );
         map.insert(
             "code_publish_package_txn".to_string(),
             Box::new(decoder::code_publish_package_txn),
@@ -76,6 +76,68 @@ pub struct PackageMetadata {
     pub extension: MoveOption<Any>,
 }
 
+impl PackageMetadata {
+    /// Computes the metadata which results from applying an incremental upgrade to an existing
+    /// package, mirroring `code::publish_package_incremental`. The update only contains the
+    /// modules which are added or replaced; modules are overlaid by name, and all other modules
+    /// of the existing package are kept. Modules are only removed if they are explicitly listed
+    /// in `removed_modules`. The package level fields are taken from the update, and the upgrade
+    /// number is advanced.
+    ///
+    /// This does not check the upgrade policy, which is done on-chain when the upgrade is
+    /// applied to the registry.
+    pub fn merge_incremental(
+        existing: &PackageMetadata,
+        update: PackageMetadata,
+        removed_modules: &[String],
+    ) -> anyhow::Result<PackageMetadata> {
+        if existing.name != update.name {
+            bail!(
+                "cannot apply incremental upgrade of package `{}` to package `{}`",
+                update.name,
+                existing.name
+            )
+        }
+        for name in removed_modules {
+            if !existing.modules.iter().any(|m| &m.name == name) {
+                bail!("removed module `{}` is not part of the package", name)
+            }
+            if update.modules.iter().any(|m| &m.name == name) {
+                bail!("module `{}` is both removed and republished", name)
+            }
+        }
+        let PackageMetadata {
+            name,
+            upgrade_policy,
+            upgrade_number: _,
+            source_digest,
+            manifest,
+            modules: mut changed,
+            deps,
+            extension,
+        } = update;
+        let mut modules = vec![];
+        for old in &existing.modules {
+            if let Some(pos) = changed.iter().position(|m| m.name == old.name) {
+                modules.push(changed.remove(pos))
+            } else if !removed_modules.contains(&old.name) {
+                modules.push(old.clone())
+            }
+        }
+        modules.append(&mut changed);
+        Ok(PackageMetadata {
+            name,
+            upgrade_policy,
+            upgrade_number: existing.upgrade_number + 1,
+            source_digest,
+            manifest,
+            modules,
+            deps,
+            extension,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
 pub struct PackageDep {
     pub account: AccountAddress,
@@ -315,3 +377,67 @@ pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, Nati
 
     crate::natives::helpers::make_module_natives(natives)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(name: &str, source: &[u8]) -> ModuleMetadata {
+        ModuleMetadata {
+            name: name.to_string(),
+            source: source.to_vec(),
+            source_map: vec![],
+            extension: MoveOption::none(),
+        }
+    }
+
+    fn package(modules: Vec<ModuleMetadata>) -> PackageMetadata {
+        PackageMetadata {
+            name: "Package".to_string(),
+            upgrade_policy: UpgradePolicy::compat(),
+            upgrade_number: 0,
+            source_digest: String::new(),
+            manifest: vec![],
+            modules,
+            deps: vec![],
+            extension: MoveOption::none(),
+        }
+    }
+
+    #[test]
+    fn test_merge_incremental() {
+        let existing = package(vec![module("a", b"a"), module("b", b"b")]);
+
+        // Add
+        let merged =
+            PackageMetadata::merge_incremental(&existing, package(vec![module("c", b"c")]), &[])
+                .unwrap();
+        assert_eq!(merged.upgrade_number, 1);
+        assert_eq!(
+            merged.modules,
+            vec![module("a", b"a"), module("b", b"b"), module("c", b"c")]
+        );
+
+        // Replace
+        let merged =
+            PackageMetadata::merge_incremental(&existing, package(vec![module("a", b"a2")]), &[])
+                .unwrap();
+        assert_eq!(merged.modules, vec![module("a", b"a2"), module("b", b"b")]);
+
+        // Tombstone
+        let merged =
+            PackageMetadata::merge_incremental(&existing, package(vec![]), &["a".to_string()])
+                .unwrap();
+        assert_eq!(merged.modules, vec![module("b", b"b")]);
+        assert!(
+            PackageMetadata::merge_incremental(&existing, package(vec![]), &["c".to_string()])
+                .is_err()
+        );
+        assert!(PackageMetadata::merge_incremental(
+            &existing,
+            package(vec![module("a", b"a2")]),
+            &["a".to_string()]
+        )
+        .is_err());
+    }
+}
@@ -31,6 +31,10 @@ pub struct ConsensusConfig {
     // Decides how long the leader waits before proposing empty block if there's no txns in mempool
     // the period = (poll_count - 1) * 30ms
     pub quorum_store_poll_count: u64,
+    // Per-peer limits on quorum store data received within a sliding window
+    pub quorum_store_peer_quota_window_ms: u64,
+    pub quorum_store_peer_quota_max_bytes: u64,
+    pub quorum_store_peer_quota_max_messages: u64,
     pub intra_consensus_channel_buffer_size: usize,
 
     // Used to decide if backoff is needed.
@@ -71,6 +75,9 @@ impl Default for ConsensusConfig {
 
             quorum_store_pull_timeout_ms: 1000,
             quorum_store_poll_count: 10,
+            quorum_store_peer_quota_window_ms: 1000,
+            quorum_store_peer_quota_max_bytes: 10 * 1024 * 1024, // 10MB
+            quorum_store_peer_quota_max_messages: 1000,
             intra_consensus_channel_buffer_size: 10,
 
             window_for_chain_health: 100,
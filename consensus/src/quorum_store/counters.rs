@@ -1,7 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 use aptos_metrics_core::{
-    op_counters::DurationHistogram, register_histogram, register_histogram_vec, HistogramVec,
+    op_counters::DurationHistogram, register_histogram, register_histogram_vec,
+    register_int_counter, HistogramVec, IntCounter,
 };
 use once_cell::sync::Lazy;
 use std::time::Duration;
@@ -42,3 +43,21 @@ pub static MAIN_LOOP: Lazy<DurationHistogram> = Lazy::new(|| {
         .unwrap(),
     )
 });
+
+/// Count of the proofs of store which expired before they could be included in a payload.
+pub static EXPIRED_PROOFS_SKIPPED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_expired_proofs_skipped",
+        "Count of the proofs of store which expired before they could be included in a payload."
+    )
+    .unwrap()
+});
+
+/// Count of the messages dropped because their sender exceeded its quota.
+pub static PEER_QUOTA_EXCEEDED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_peer_quota_exceeded",
+        "Count of the messages dropped because their sender exceeded its quota."
+    )
+    .unwrap()
+});
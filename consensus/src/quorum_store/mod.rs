@@ -6,5 +6,9 @@ pub mod direct_mempool_quorum_store;
 
 pub(crate) mod batch_reader;
 mod counters;
+pub(crate) mod payload_assembler;
+pub(crate) mod peer_quota;
+pub(crate) mod types;
+pub(crate) mod utils;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,75 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::counters;
+use aptos_consensus_types::{
+    common::{Payload, ProofWithData},
+    proof_of_store::{LogicalTime, ProofOfStore},
+};
+
+/// Collects completed proofs of store and packs them into block payloads.
+///
+/// Proofs are preferred by earliest expiration, so that batches are proposed before they
+/// expire, and then by size, so that small batches can fill up the remaining space.
+/// Proofs which do not fit into a payload are kept for the next one.
+#[derive(Default)]
+#[allow(dead_code)]
+pub(crate) struct PayloadAssembler {
+    proofs: Vec<ProofOfStore>,
+}
+
+#[allow(dead_code)]
+impl PayloadAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, proof: ProofOfStore) {
+        self.proofs.push(proof);
+    }
+
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Builds a payload from the collected proofs, with at most `max_txns` transactions and
+    /// `max_bytes` bytes in total according to the proofs' `num_txns` and `num_bytes`.
+    /// Proofs which expired before `current_time` are dropped.
+    pub fn assemble(
+        &mut self,
+        current_time: LogicalTime,
+        max_txns: u64,
+        max_bytes: u64,
+    ) -> Payload {
+        self.proofs
+            .sort_by_key(|proof| (proof.expiration(), proof.info().num_bytes));
+
+        let mut txns = 0;
+        let mut bytes = 0;
+        let mut selected = Vec::new();
+        let mut remaining = Vec::new();
+        for proof in self.proofs.drain(..) {
+            if proof.expiration() < current_time {
+                counters::EXPIRED_PROOFS_SKIPPED.inc();
+                continue;
+            }
+            let info = proof.info();
+            // The counts are advertised by peers, so compare against the remaining budget to avoid
+            // overflows. `txns <= max_txns` and `bytes <= max_bytes` always hold.
+            if info.num_txns <= max_txns - txns && info.num_bytes <= max_bytes - bytes {
+                txns += info.num_txns;
+                bytes += info.num_bytes;
+                selected.push(proof);
+            } else {
+                remaining.push(proof);
+            }
+        }
+        self.proofs = remaining;
+
+        Payload::InQuorumStore(ProofWithData::new(selected))
+    }
+}
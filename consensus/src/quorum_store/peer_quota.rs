@@ -0,0 +1,120 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::counters;
+use aptos_config::config::ConsensusConfig;
+use aptos_types::PeerId;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum PeerQuotaError {
+    #[error("Peer {peer} exceeded byte quota: {bytes} bytes in window, limit {limit}")]
+    BytesExceeded {
+        peer: PeerId,
+        bytes: u64,
+        limit: u64,
+    },
+    #[error("Peer {peer} exceeded message quota: {messages} messages in window, limit {limit}")]
+    MessagesExceeded {
+        peer: PeerId,
+        messages: u64,
+        limit: u64,
+    },
+}
+
+/// Messages received from a single peer within the current window.
+#[derive(Default)]
+struct PeerUsage {
+    // (receive time, message size in bytes), oldest first
+    received: VecDeque<(Instant, u64)>,
+    bytes: u64,
+}
+
+impl PeerUsage {
+    fn evict_before(&mut self, cutoff: Instant) {
+        while let Some((time, bytes)) = self.received.front() {
+            if *time >= cutoff {
+                break;
+            }
+            self.bytes -= bytes;
+            self.received.pop_front();
+        }
+    }
+}
+
+/// Tracks how much quorum store data every peer sent us over a sliding window, so that a single
+/// peer cannot flood us and evict honest peers' data from memory. Should be consulted with the
+/// raw message size before any payload is deserialized.
+#[allow(dead_code)]
+pub(crate) struct PeerQuota {
+    window: Duration,
+    max_bytes: u64,
+    max_messages: u64,
+    usage: HashMap<PeerId, PeerUsage>,
+}
+
+#[allow(dead_code)]
+impl PeerQuota {
+    pub fn new(window: Duration, max_bytes: u64, max_messages: u64) -> Self {
+        Self {
+            window,
+            max_bytes,
+            max_messages,
+            usage: HashMap::new(),
+        }
+    }
+
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self::new(
+            Duration::from_millis(config.quorum_store_peer_quota_window_ms),
+            config.quorum_store_peer_quota_max_bytes,
+            config.quorum_store_peer_quota_max_messages,
+        )
+    }
+
+    /// Records a message of `num_bytes` from `peer` received at `now`. Messages which would put
+    /// the peer over quota are not recorded and return an error.
+    pub fn check_and_record(
+        &mut self,
+        peer: PeerId,
+        num_bytes: u64,
+        now: Instant,
+    ) -> Result<(), PeerQuotaError> {
+        let usage = self.usage.entry(peer).or_default();
+        if let Some(cutoff) = now.checked_sub(self.window) {
+            usage.evict_before(cutoff);
+        }
+
+        let messages = usage.received.len() as u64 + 1;
+        if messages > self.max_messages {
+            counters::PEER_QUOTA_EXCEEDED.inc();
+            return Err(PeerQuotaError::MessagesExceeded {
+                peer,
+                messages,
+                limit: self.max_messages,
+            });
+        }
+        let bytes = usage.bytes + num_bytes;
+        if bytes > self.max_bytes {
+            counters::PEER_QUOTA_EXCEEDED.inc();
+            return Err(PeerQuotaError::BytesExceeded {
+                peer,
+                bytes,
+                limit: self.max_bytes,
+            });
+        }
+
+        usage.received.push_back((now, num_bytes));
+        usage.bytes = bytes;
+        Ok(())
+    }
+
+    /// Forgets all recorded usage, e.g. at an epoch boundary.
+    pub fn reset(&mut self) {
+        self.usage.clear();
+    }
+}
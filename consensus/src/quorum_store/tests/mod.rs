@@ -3,3 +3,11 @@
 
 #[cfg(test)]
 mod direct_mempool_quorum_store_test;
+#[cfg(test)]
+mod payload_assembler_test;
+#[cfg(test)]
+mod peer_quota_test;
+#[cfg(test)]
+mod types_test;
+#[cfg(test)]
+mod utils_test;
@@ -0,0 +1,113 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::payload_assembler::PayloadAssembler;
+use aptos_consensus_types::{
+    common::Payload,
+    proof_of_store::{LogicalTime, ProofOfStore, SignedDigestInfo},
+};
+use aptos_crypto::HashValue;
+use aptos_types::aggregate_signature::AggregateSignature;
+
+fn proof(round: u64, num_txns: u64, num_bytes: u64) -> ProofOfStore {
+    ProofOfStore::new(
+        SignedDigestInfo {
+            digest: HashValue::random(),
+            expiration: LogicalTime::new(1, round),
+            num_txns,
+            num_bytes,
+        },
+        AggregateSignature::empty(),
+    )
+}
+
+fn proofs(payload: Payload) -> Vec<ProofOfStore> {
+    match payload {
+        Payload::InQuorumStore(proof_with_data) => proof_with_data.proofs,
+        Payload::DirectMempool(_) => panic!("unexpected payload type"),
+    }
+}
+
+#[test]
+fn test_assemble_respects_both_limits() {
+    let mut assembler = PayloadAssembler::new();
+    // Many small transactions
+    let small_txns = proof(10, 50, 500);
+    // Fits into the byte limit, but not into the transaction limit after `small_txns`
+    let many_txns = proof(11, 20, 100);
+    // Fits into the transaction limit, but not into the byte limit
+    let large_txns = proof(12, 5, 5_000);
+    // Fits into both limits together with `small_txns`
+    let filler = proof(13, 10, 1_000);
+    assembler.push(filler.clone());
+    assembler.push(large_txns.clone());
+    assembler.push(many_txns.clone());
+    assembler.push(small_txns.clone());
+
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 60, 2_000));
+    assert_eq!(selected, vec![small_txns, filler]);
+    assert_eq!(assembler.len(), 2);
+
+    // The held back proofs are selected once the limits allow it.
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 60, 2_000));
+    assert_eq!(selected, vec![many_txns]);
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 60, 5_000));
+    assert_eq!(selected, vec![large_txns]);
+    assert!(assembler.is_empty());
+}
+
+#[test]
+fn test_assemble_orders_by_expiration() {
+    let mut assembler = PayloadAssembler::new();
+    let late = proof(20, 10, 100);
+    let early = proof(10, 10, 100);
+    assembler.push(late.clone());
+    assembler.push(early.clone());
+
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 10, 1_000));
+    assert_eq!(selected, vec![early]);
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 10, 1_000));
+    assert_eq!(selected, vec![late]);
+}
+
+#[test]
+fn test_assemble_skips_expired() {
+    let mut assembler = PayloadAssembler::new();
+    let expired = proof(5, 1, 10);
+    let old_epoch = ProofOfStore::new(
+        SignedDigestInfo {
+            digest: HashValue::random(),
+            expiration: LogicalTime::new(0, 100),
+            num_txns: 1,
+            num_bytes: 10,
+        },
+        AggregateSignature::empty(),
+    );
+    let valid = proof(6, 1, 10);
+    assembler.push(expired);
+    assembler.push(old_epoch);
+    assembler.push(valid.clone());
+
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 6), 100, 1_000));
+    assert_eq!(selected, vec![valid]);
+    assert!(assembler.is_empty());
+}
+
+#[test]
+fn test_assemble_oversized_counts() {
+    let mut assembler = PayloadAssembler::new();
+    let normal = proof(10, 10, 100);
+    let oversized = proof(11, u64::MAX, u64::MAX);
+    assembler.push(oversized.clone());
+    assembler.push(normal.clone());
+
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), 100, 1_000));
+    assert_eq!(selected, vec![normal]);
+    assert_eq!(assembler.len(), 1);
+
+    // Held back even with the maximum budget.
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), u64::MAX - 1, u64::MAX));
+    assert!(selected.is_empty());
+    let selected = proofs(assembler.assemble(LogicalTime::new(1, 1), u64::MAX, u64::MAX));
+    assert_eq!(selected, vec![oversized]);
+}
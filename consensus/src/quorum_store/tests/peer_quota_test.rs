@@ -0,0 +1,75 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::peer_quota::{PeerQuota, PeerQuotaError};
+use aptos_types::PeerId;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_peer_over_byte_quota() {
+    let mut quota = PeerQuota::new(Duration::from_secs(1), 1000, 100);
+    let flooder = PeerId::random();
+    let honest = PeerId::random();
+    let now = Instant::now();
+
+    for _ in 0..10 {
+        quota.check_and_record(flooder, 100, now).unwrap();
+    }
+    assert_eq!(
+        quota.check_and_record(flooder, 100, now),
+        Err(PeerQuotaError::BytesExceeded {
+            peer: flooder,
+            bytes: 1100,
+            limit: 1000,
+        })
+    );
+    // Other peers are not affected.
+    quota.check_and_record(honest, 500, now).unwrap();
+
+    // Once the window has passed, the flooder is accepted again.
+    quota
+        .check_and_record(flooder, 100, now + Duration::from_secs(2))
+        .unwrap();
+}
+
+#[test]
+fn test_peer_over_message_quota() {
+    let mut quota = PeerQuota::new(Duration::from_secs(1), 1000, 3);
+    let flooder = PeerId::random();
+    let honest = PeerId::random();
+    let now = Instant::now();
+
+    for i in 0..3 {
+        quota
+            .check_and_record(flooder, 1, now + Duration::from_millis(i * 400))
+            .unwrap();
+    }
+    assert_eq!(
+        quota.check_and_record(flooder, 1, now + Duration::from_millis(900)),
+        Err(PeerQuotaError::MessagesExceeded {
+            peer: flooder,
+            messages: 4,
+            limit: 3,
+        })
+    );
+    quota
+        .check_and_record(honest, 1, now + Duration::from_millis(900))
+        .unwrap();
+
+    // The first message slides out of the window, freeing up one slot.
+    let later = now + Duration::from_millis(1100);
+    quota.check_and_record(flooder, 1, later).unwrap();
+    assert!(quota.check_and_record(flooder, 1, later).is_err());
+}
+
+#[test]
+fn test_reset() {
+    let mut quota = PeerQuota::new(Duration::from_secs(1), 100, 100);
+    let peer = PeerId::random();
+    let now = Instant::now();
+
+    quota.check_and_record(peer, 100, now).unwrap();
+    assert!(quota.check_and_record(peer, 1, now).is_err());
+    quota.reset();
+    quota.check_and_record(peer, 100, now).unwrap();
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::types::{ExpirationBuckets, PersistedValue};
+use aptos_consensus_types::proof_of_store::LogicalTime;
+use aptos_crypto::HashValue;
+use aptos_types::PeerId;
+use std::collections::HashSet;
+
+#[test]
+fn test_persisted_value_serialization() {
+    let mut value = PersistedValue::new(Some(vec![]), LogicalTime::new(1, 10), PeerId::random(), 0);
+    let bytes = bcs::to_bytes(&value).unwrap();
+    assert_eq!(bcs::from_bytes::<PersistedValue>(&bytes).unwrap(), value);
+
+    value.remove_payload();
+    let bytes = bcs::to_bytes(&value).unwrap();
+    let deserialized: PersistedValue = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(deserialized.maybe_payload, None);
+    assert_eq!(deserialized, value);
+}
+
+#[test]
+fn test_expiration_buckets_insert_expire_cycles() {
+    let author = PeerId::random();
+    let mut buckets = ExpirationBuckets::new();
+    let mut expected_bytes = 0;
+
+    for round in 1..=10u64 {
+        // Every round inserts values expiring within the next three rounds.
+        let mut inserted = Vec::new();
+        for offset in 1..=3u64 {
+            let digest = HashValue::random();
+            let value = PersistedValue::new(None, LogicalTime::new(1, round + offset), author, 100);
+            buckets.add(digest, &value);
+            expected_bytes += value.num_bytes;
+            inserted.push(digest);
+        }
+
+        // Values expiring at or before the certified round are cleared, others remain.
+        let expired: HashSet<_> = buckets
+            .clear_expired(LogicalTime::new(1, round))
+            .into_iter()
+            .collect();
+        let expected_expired = std::cmp::min(round - 1, 3) as usize;
+        assert_eq!(expired.len(), expected_expired);
+        assert!(inserted.iter().all(|digest| !expired.contains(digest)));
+        expected_bytes -= expired.len() * 100;
+        assert_eq!(buckets.num_bytes(), expected_bytes);
+    }
+
+    // A new epoch expires everything from the previous one.
+    let expired = buckets.clear_expired(LogicalTime::new(2, 0));
+    assert_eq!(expired.len() * 100, expected_bytes);
+    assert_eq!(buckets.num_bytes(), 0);
+    assert!(buckets.is_empty());
+}
+
+#[test]
+fn test_expiration_buckets_extended_digest() {
+    let author = PeerId::random();
+    let mut buckets = ExpirationBuckets::new();
+    let digest = HashValue::random();
+
+    buckets.add(
+        digest,
+        &PersistedValue::new(None, LogicalTime::new(1, 5), author, 100),
+    );
+    // Adding the digest again extends its expiration without double counting its bytes.
+    buckets.add(
+        digest,
+        &PersistedValue::new(None, LogicalTime::new(1, 10), author, 100),
+    );
+    assert_eq!(buckets.num_bytes(), 100);
+    assert!(buckets.clear_expired(LogicalTime::new(1, 9)).is_empty());
+
+    // An earlier expiration does not shorten it again.
+    buckets.add(
+        digest,
+        &PersistedValue::new(None, LogicalTime::new(1, 7), author, 100),
+    );
+    assert!(buckets.clear_expired(LogicalTime::new(1, 9)).is_empty());
+    assert_eq!(buckets.num_bytes(), 100);
+
+    // A value expiring exactly at the certified time is expired.
+    assert_eq!(buckets.clear_expired(LogicalTime::new(1, 10)), vec![digest]);
+    assert_eq!(buckets.num_bytes(), 0);
+    assert!(buckets.is_empty());
+}
@@ -0,0 +1,151 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::utils::{num_expired, prune_expired, ExpirationQueue};
+use aptos_consensus_types::proof_of_store::LogicalTime;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, Uniform};
+use aptos_types::{
+    account_address::AccountAddress,
+    test_helpers::transaction_test_helpers::get_test_signed_transaction,
+    transaction::SignedTransaction,
+};
+use proptest::{collection::vec, prelude::*};
+use std::{
+    cmp::max,
+    collections::{HashMap, HashSet},
+};
+
+fn create_txns(expirations: &[u64]) -> Vec<SignedTransaction> {
+    let sender = AccountAddress::random();
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    expirations
+        .iter()
+        .enumerate()
+        .map(|(i, expiration)| {
+            get_test_signed_transaction(
+                sender,
+                i as u64,
+                &private_key,
+                private_key.public_key(),
+                None,
+                *expiration,
+                1,
+                None,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_prune_expired_boundaries() {
+    let txns = create_txns(&[100, 109, 110, 111, 200]);
+    let (remaining, num_pruned) = prune_expired(txns.clone(), 100, 10);
+
+    // A transaction expiring exactly at the end of the budget is already expired then.
+    assert_eq!(num_pruned, 3);
+    assert_eq!(remaining, vec![txns[3].clone(), txns[4].clone()]);
+}
+
+#[test]
+fn test_prune_expired_all() {
+    let txns = create_txns(&[1, 2, 3]);
+    let (remaining, num_pruned) = prune_expired(txns, 100, 0);
+    assert!(remaining.is_empty());
+    assert_eq!(num_pruned, 3);
+
+    let (remaining, num_pruned) = prune_expired(vec![], 100, 10);
+    assert!(remaining.is_empty());
+    assert_eq!(num_pruned, 0);
+}
+
+#[test]
+fn test_num_expired_boundaries() {
+    let txns = create_txns(&[99, 100, 101, 200]);
+    // A transaction expiring exactly now is already expired.
+    assert_eq!(num_expired(&txns, 100), 2);
+    assert_eq!(num_expired(&txns, 98), 0);
+    assert_eq!(num_expired(&txns, 200), 4);
+    assert_eq!(num_expired(&[], 100), 0);
+
+    // Consistent with pruning without a latency budget.
+    let (_, num_pruned) = prune_expired(txns.clone(), 100, 0);
+    assert_eq!(num_pruned, num_expired(&txns, 100));
+}
+
+#[test]
+fn test_expiration_queue_duplicates_and_epochs() {
+    let mut queue = ExpirationQueue::new();
+    let digest = HashValue::random();
+    let other = HashValue::random();
+
+    queue.insert(LogicalTime::new(1, 10), digest);
+    // An earlier expiration for the same digest does not shorten its lifetime.
+    queue.insert(LogicalTime::new(1, 5), digest);
+    queue.insert(LogicalTime::new(1, 20), other);
+    assert_eq!(queue.len(), 2);
+    assert!(queue.expire_upto(LogicalTime::new(1, 9)).is_empty());
+
+    // A later expiration extends it.
+    queue.insert(LogicalTime::new(1, 15), digest);
+    assert!(queue.expire_upto(LogicalTime::new(1, 10)).is_empty());
+    assert_eq!(queue.expire_upto(LogicalTime::new(1, 15)), vec![digest]);
+
+    // Moving to a new epoch expires everything from the previous one.
+    assert_eq!(queue.expire_upto(LogicalTime::new(2, 0)), vec![other]);
+    assert!(queue.is_empty());
+
+    queue.insert(LogicalTime::new(2, 3), digest);
+    assert_eq!(queue.remove(&digest), Some(LogicalTime::new(2, 3)));
+    assert_eq!(queue.remove(&digest), None);
+    assert!(queue.expire_upto(LogicalTime::new(2, 3)).is_empty());
+}
+
+#[derive(Clone, Debug)]
+enum QueueOp {
+    Insert(LogicalTime, usize),
+    Remove(usize),
+    Expire(LogicalTime),
+}
+
+fn queue_op() -> impl Strategy<Value = QueueOp> {
+    // Few digests, epochs and rounds so that duplicates, collisions and epoch changes are common.
+    let time = (0..3u64, 0..20u64).prop_map(|(epoch, round)| LogicalTime::new(epoch, round));
+    prop_oneof![
+        (time.clone(), 0..8usize).prop_map(|(time, digest)| QueueOp::Insert(time, digest)),
+        (0..8usize).prop_map(QueueOp::Remove),
+        time.prop_map(QueueOp::Expire),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_expiration_queue_against_model(ops in vec(queue_op(), 0..100)) {
+        let digests: Vec<_> = (0..8).map(|_| HashValue::random()).collect();
+        let mut queue = ExpirationQueue::new();
+        let mut model: HashMap<HashValue, LogicalTime> = HashMap::new();
+
+        for op in ops {
+            match op {
+                QueueOp::Insert(expiration, i) => {
+                    queue.insert(expiration, digests[i]);
+                    let entry = model.entry(digests[i]).or_insert(expiration);
+                    *entry = max(*entry, expiration);
+                },
+                QueueOp::Remove(i) => {
+                    prop_assert_eq!(queue.remove(&digests[i]), model.remove(&digests[i]));
+                },
+                QueueOp::Expire(certified) => {
+                    let expired: HashSet<_> = queue.expire_upto(certified).into_iter().collect();
+                    let expected: HashSet<_> = model
+                        .iter()
+                        .filter(|(_, expiration)| **expiration <= certified)
+                        .map(|(digest, _)| *digest)
+                        .collect();
+                    model.retain(|_, expiration| *expiration > certified);
+                    prop_assert_eq!(expired, expected);
+                },
+            }
+            prop_assert_eq!(queue.len(), model.len());
+        }
+    }
+}
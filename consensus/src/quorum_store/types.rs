@@ -0,0 +1,86 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::utils::ExpirationQueue;
+use aptos_consensus_types::proof_of_store::LogicalTime;
+use aptos_crypto::HashValue;
+use aptos_types::{transaction::SignedTransaction, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The persisted form of a batch we authored or certified. The payload may be dropped
+/// (e.g. once the batch is committed) while the metadata is kept until expiration.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PersistedValue {
+    pub maybe_payload: Option<Vec<SignedTransaction>>,
+    pub expiration: LogicalTime,
+    pub author: PeerId,
+    pub num_bytes: usize,
+}
+
+#[allow(dead_code)]
+impl PersistedValue {
+    pub fn new(
+        maybe_payload: Option<Vec<SignedTransaction>>,
+        expiration: LogicalTime,
+        author: PeerId,
+        num_bytes: usize,
+    ) -> Self {
+        Self {
+            maybe_payload,
+            expiration,
+            author,
+            num_bytes,
+        }
+    }
+
+    pub fn remove_payload(&mut self) {
+        self.maybe_payload = None;
+    }
+}
+
+/// Indexes persisted digests by expiration, so that everything eligible for garbage collection
+/// can be found without scanning the whole store. Also keeps track of the total number of bytes
+/// of the indexed values for quota accounting. A digest added again is kept until the latest of
+/// its expirations and is only accounted for once.
+#[derive(Default)]
+#[allow(dead_code)]
+pub(crate) struct ExpirationBuckets {
+    expirations: ExpirationQueue,
+    digest_num_bytes: HashMap<HashValue, usize>,
+    num_bytes: usize,
+}
+
+#[allow(dead_code)]
+impl ExpirationBuckets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, digest: HashValue, value: &PersistedValue) {
+        self.expirations.insert(value.expiration, digest);
+        if let Some(prev_num_bytes) = self.digest_num_bytes.insert(digest, value.num_bytes) {
+            self.num_bytes -= prev_num_bytes;
+        }
+        self.num_bytes += value.num_bytes;
+    }
+
+    /// Removes and returns the digests of all values which expire at or before `certified_time`.
+    pub fn clear_expired(&mut self, certified_time: LogicalTime) -> Vec<HashValue> {
+        let digests = self.expirations.expire_upto(certified_time);
+        for digest in &digests {
+            if let Some(num_bytes) = self.digest_num_bytes.remove(digest) {
+                self.num_bytes -= num_bytes;
+            }
+        }
+        digests
+    }
+
+    pub fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expirations.is_empty()
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::proof_of_store::LogicalTime;
+use aptos_crypto::HashValue;
+use aptos_types::transaction::SignedTransaction;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
+
+/// Removes the transactions which will have expired by the time a batch created now can be
+/// committed, i.e. within `batch_latency_budget_secs` from `now_secs`. Returns the remaining
+/// transactions (in their original order) and the number of transactions removed.
+#[allow(dead_code)]
+pub(crate) fn prune_expired(
+    txns: Vec<SignedTransaction>,
+    now_secs: u64,
+    batch_latency_budget_secs: u64,
+) -> (Vec<SignedTransaction>, usize) {
+    let deadline = now_secs.saturating_add(batch_latency_budget_secs);
+    let num_txns = txns.len();
+    let remaining: Vec<_> = txns
+        .into_iter()
+        .filter(|txn| !is_expired(txn, deadline))
+        .collect();
+    let num_pruned = num_txns - remaining.len();
+    (remaining, num_pruned)
+}
+
+/// Counts the transactions which are already expired at `now_secs`, so that received batches
+/// containing many of them can be flagged. Uses the same boundary as `prune_expired`.
+#[allow(dead_code)]
+pub(crate) fn num_expired(txns: &[SignedTransaction], now_secs: u64) -> usize {
+    txns.iter().filter(|txn| is_expired(txn, now_secs)).count()
+}
+
+fn is_expired(txn: &SignedTransaction, time_secs: u64) -> bool {
+    txn.expiration_timestamp_secs() <= time_secs
+}
+
+/// Keeps track of digests by their expiration, so that everything which expired can be garbage
+/// collected at once. A digest inserted multiple times is kept until its latest expiration.
+/// As `LogicalTime` is ordered by epoch first, moving to a new epoch expires all digests from
+/// the older epochs.
+#[derive(Default)]
+#[allow(dead_code)]
+pub(crate) struct ExpirationQueue {
+    expirations: BTreeMap<LogicalTime, HashSet<HashValue>>,
+    digests: HashMap<HashValue, LogicalTime>,
+}
+
+#[allow(dead_code)]
+impl ExpirationQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, expiration: LogicalTime, digest: HashValue) {
+        match self.digests.entry(digest) {
+            Entry::Occupied(mut entry) => {
+                if *entry.get() >= expiration {
+                    return;
+                }
+                let previous = entry.insert(expiration);
+                Self::remove_from_bucket(&mut self.expirations, previous, &digest);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(expiration);
+            }
+        }
+        self.expirations
+            .entry(expiration)
+            .or_default()
+            .insert(digest);
+    }
+
+    /// Returns the expiration the digest was tracked with, if any.
+    pub fn remove(&mut self, digest: &HashValue) -> Option<LogicalTime> {
+        let expiration = self.digests.remove(digest)?;
+        Self::remove_from_bucket(&mut self.expirations, expiration, digest);
+        Some(expiration)
+    }
+
+    /// Removes and returns all digests which expire at or before `certified`.
+    pub fn expire_upto(&mut self, certified: LogicalTime) -> Vec<HashValue> {
+        let expired_times: Vec<_> = self
+            .expirations
+            .range(..=certified)
+            .map(|(t, _)| *t)
+            .collect();
+        let mut expired = Vec::new();
+        for time in expired_times {
+            for digest in self.expirations.remove(&time).unwrap_or_default() {
+                self.digests.remove(&digest);
+                expired.push(digest);
+            }
+        }
+        expired
+    }
+
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    fn remove_from_bucket(
+        expirations: &mut BTreeMap<LogicalTime, HashSet<HashValue>>,
+        expiration: LogicalTime,
+        digest: &HashValue,
+    ) {
+        if let Some(bucket) = expirations.get_mut(&expiration) {
+            bucket.remove(digest);
+            if bucket.is_empty() {
+                expirations.remove(&expiration);
+            }
+        }
+    }
+}